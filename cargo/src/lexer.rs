@@ -0,0 +1,142 @@
+//! A small logos-style lexer: scans the whole source into a flat, ordered token stream up
+//! front, instead of matching prefixes against one line at a time. Downstream parsing (statement
+//! syntax, block start/end tokens, operator recognition) matches against this stream, and its
+//! byte offsets are what feed the span-based diagnostics (a human-facing line/column is derived
+//! from an offset on demand, via `locate`, rather than carried redundantly on every token).
+
+/// What kind of thing a token is. `Symbol` covers both configured operators and the punctuation
+/// (`(`, `)`, `,`) callers ask `tokenize` to recognize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A run of characters that isn't whitespace, a quote, or a recognized symbol: identifiers, numbers, bare words.
+    Word,
+    /// A recognized symbol, matched longest-first against the list passed to `tokenize`.
+    Symbol,
+    /// A double-quoted string literal. `text` holds the content with the surrounding quotes stripped;
+    /// a backslash is dropped and the character after it kept verbatim (so `\"` gives `"`, but `\n`
+    /// gives a plain `n`, not a newline). It survives whitespace splitting as a single token.
+    StringLit,
+    Newline,
+}
+
+/// A single lexed token: its kind, its text, and the byte range it occupied in the source.
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Scan `source` into a flat token stream. `symbols` (operators plus any punctuation the caller
+/// cares about, e.g. `(`, `)`, `,`) are matched longest-first, so a multi-character symbol like
+/// `>=` wins over a shorter one (`>`) that shares its prefix.
+pub fn tokenize(source: &str, symbols: &[String]) -> Vec<Token> {
+    let mut sorted_symbols: Vec<&str> = symbols.iter().map(String::as_str).collect();
+    sorted_symbols.sort_by_key(|s| std::cmp::Reverse(s.len()));
+
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = source.chars().collect();
+    let mut i = 0;
+    let mut offset = 0usize;
+
+    let mut word = String::new();
+    let mut word_start: Option<usize> = None;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let char_len = c.len_utf8();
+
+        if c == '\n' {
+            flush_word(&mut word, &mut word_start, &mut tokens);
+            tokens.push(Token {
+                kind: TokenKind::Newline,
+                text: "\n".to_string(),
+                start: offset,
+                end: offset + char_len,
+            });
+            offset += char_len;
+            i += 1;
+            continue;
+        }
+
+        if c.is_whitespace() {
+            flush_word(&mut word, &mut word_start, &mut tokens);
+            offset += char_len;
+            i += 1;
+            continue;
+        }
+
+        if c == '"' {
+            flush_word(&mut word, &mut word_start, &mut tokens);
+            let start = offset;
+            i += 1;
+            offset += char_len;
+
+            let mut content = String::new();
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    content.push(chars[i + 1]);
+                    offset += chars[i].len_utf8() + chars[i + 1].len_utf8();
+                    i += 2;
+                } else {
+                    content.push(chars[i]);
+                    offset += chars[i].len_utf8();
+                    i += 1;
+                }
+            }
+            if i < chars.len() {
+                // Closing quote.
+                offset += chars[i].len_utf8();
+                i += 1;
+            }
+
+            tokens.push(Token {
+                kind: TokenKind::StringLit,
+                text: content,
+                start,
+                end: offset,
+            });
+            continue;
+        }
+
+        let rest: String = chars[i..].iter().collect();
+        if let Some(symbol) = sorted_symbols.iter().find(|s| rest.starts_with(*s)) {
+            flush_word(&mut word, &mut word_start, &mut tokens);
+            let len_chars = symbol.chars().count();
+            tokens.push(Token {
+                kind: TokenKind::Symbol,
+                text: symbol.to_string(),
+                start: offset,
+                end: offset + symbol.len(),
+            });
+            offset += symbol.len();
+            i += len_chars;
+            continue;
+        }
+
+        if word.is_empty() {
+            word_start = Some(offset);
+        }
+        word.push(c);
+        offset += char_len;
+        i += 1;
+    }
+    flush_word(&mut word, &mut word_start, &mut tokens);
+
+    tokens
+}
+
+fn flush_word(word: &mut String, word_start: &mut Option<usize>, tokens: &mut Vec<Token>) {
+    if word.is_empty() {
+        return;
+    }
+    let start = word_start.take().unwrap();
+    tokens.push(Token {
+        kind: TokenKind::Word,
+        text: word.clone(),
+        start,
+        end: start + word.len(),
+    });
+    word.clear();
+}
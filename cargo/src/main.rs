@@ -1,3 +1,6 @@
+mod lexer;
+
+use lexer::{Token, TokenKind};
 use serde::{Deserialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -7,7 +10,8 @@ use std::{
     process,
 };
 
-/// The overall config for the language definition. Notice that every aspect of the language is defined in JSON.
+/// The overall config for the language definition. Notice that every aspect of the language is defined in JSON,
+/// including which backend targets (C, Python, ...) the language can be generated for.
 #[derive(Deserialize, Debug)]
 struct LanguageConfig {
     /// Define the different types of statements. The key is an identifier (like "print" or "assignment")
@@ -16,6 +20,8 @@ struct LanguageConfig {
     blocks: HashMap<String, BlockDef>,
     /// Operators (and their evaluation templates) so that arithmetic or other operations can be fully configured.
     operators: HashMap<String, OperatorDef>,
+    /// Backend targets this language can be generated for (e.g. "c", "python"). Selected via `--target`.
+    targets: HashMap<String, TargetDef>,
 }
 
 /// A statement definition in the language. Here, 'syntax' is a pattern or prefix that identifies the statement,
@@ -31,7 +37,15 @@ struct StatementDef {
 struct BlockDef {
     start: String,    // E.g. "if (" or "while ("
     end: String,      // E.g. "end if" or a closing brace token.
-    template: String, // E.g. "if ({condition}) {{\n{body}\n}}" — you can define placeholders.
+    // E.g. "if ({condition}) {\n{body}\n}" — `{condition}`/`{body}` are placeholders, substituted
+    // by a plain-text `.replace()` in `generate()`. There's no `{{`/`}}` escaping for a literal
+    // brace the way Rust's own `format!` has: a C-style brace in the template is just written as
+    // a single `{`/`}`, since `replace()` only ever matches the full `{condition}`/`{body}` words.
+    template: String,
+    /// How the interpreter should execute this block: "conditional" runs the body once if the
+    /// condition is truthy, "loop" re-evaluates the condition and re-runs the body until it isn't.
+    #[serde(default)]
+    kind: Option<String>,
 }
 
 /// Operator definitions let you define operations (like +, -, etc.) via a template.
@@ -39,15 +53,141 @@ struct BlockDef {
 struct OperatorDef {
     symbol: String,   // E.g. "+"
     template: String, // E.g. "({0} + {1})"
+    /// Binding power used by the Pratt parser: higher binds tighter (e.g. "*" > "+").
+    precedence: u8,
+    /// Right-associative operators (like "=") recurse with the same binding power on the right side.
+    #[serde(default)]
+    right_assoc: bool,
+}
+
+/// A backend target, describing everything needed to turn the AST into one target language's source.
+/// This is what used to be hardcoded across separate `generate_c_code`/`generate_python_code` functions.
+#[derive(Deserialize, Debug)]
+struct TargetDef {
+    /// File extension the generated source is written under (e.g. "c", "py").
+    extension: String,
+    /// Emitted once before any generated statements (e.g. a C `#include` + `int main() {`).
+    preamble: String,
+    /// Emitted once after all generated statements (e.g. C's `return 0;\n}`).
+    epilogue: String,
+    /// Indentation unit repeated per nesting level (e.g. four spaces, or a tab).
+    indent: String,
+    /// Optional line-comment prefix, for targets that support emitting comments.
+    #[serde(default)]
+    comment: Option<String>,
+    /// How to declare a freshly-assigned variable, if this target needs a declaration at all.
+    /// Python, for instance, has no `declaration` and assignment statements just use their own template.
+    #[serde(default)]
+    declaration: Option<DeclarationDef>,
+}
+
+/// The declaration emitted the first time a variable is assigned (e.g. C's `int x;`).
+#[derive(Deserialize, Debug)]
+struct DeclarationDef {
+    /// Template with `{type}` and `{0}` (the variable name) placeholders, e.g. "{type} {0};".
+    template: String,
+    /// Maps a logical type name to this target's spelling of it (e.g. {"default": "int"} for C).
+    #[serde(default)]
+    type_map: HashMap<String, String>,
+}
+
+/// A byte-offset range into the original source, used to point diagnostics and render carets.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+/// A parse-time error, carrying the span of the source it complains about.
+#[derive(Debug, Clone)]
+struct Diagnostic {
+    message: String,
+    span: Span,
+}
+
+/// Render a diagnostic as a framed snippet: a gutter with the line number, the offending
+/// line itself, and a caret underline (`^^^`) under the span — in the style of annotate-snippets/ariadne.
+fn render_diagnostic(source: &str, diag: &Diagnostic) -> String {
+    let (line_no, col, line_text) = locate(source, diag.span.start);
+    let gutter = format!("{} | ", line_no);
+    let caret_len = diag.span.end.saturating_sub(diag.span.start).max(1);
+    let underline = format!(
+        "{}{}{}",
+        " ".repeat(gutter.len()),
+        " ".repeat(col),
+        "^".repeat(caret_len)
+    );
+    format!("{}{}\n{}\nerror: {}", gutter, line_text, underline, diag.message)
+}
+
+/// Find the 1-based line number, 0-based column, and full text of the line containing `offset`.
+fn locate(source: &str, offset: usize) -> (usize, usize, &str) {
+    let mut line_no = 1;
+    let mut line_start = 0;
+    for (i, c) in source.char_indices() {
+        if i >= offset {
+            break;
+        }
+        if c == '\n' {
+            line_no += 1;
+            line_start = i + 1;
+        }
+    }
+    let line_end = source[line_start..]
+        .find('\n')
+        .map(|e| line_start + e)
+        .unwrap_or(source.len());
+    (line_no, offset - line_start, &source[line_start..line_end])
+}
+
+/// A single token produced while scanning an argument region for the expression parser.
+#[derive(Debug, Clone)]
+enum ExprToken {
+    /// A literal or identifier run (a number, a variable name, a bare word).
+    Operand(String),
+    /// A double-quoted string literal, carried as its own variant (rather than folded into
+    /// `Operand`) so its "string-ness" survives into `Expr` and codegen can re-emit the quotes —
+    /// an unquoted `Operand` and a quoted `StringLit` must not become indistinguishable once
+    /// tokenized, or `print "hello"` and `print hello` would generate identical (and for the
+    /// former, invalid) target code.
+    StringLit(String),
+    /// One of the symbols declared in `LanguageConfig.operators`.
+    Op(String),
+    LParen,
+    RParen,
+    /// Separates top-level arguments (e.g. the two arguments of `print x, y`). Kept as its
+    /// own variant (rather than folded into `Op`) so argument splitting can happen on comma
+    /// *tokens* after tokenization, instead of on raw `,` bytes that might be inside a
+    /// `StringLit`.
+    Comma,
+}
+
+/// An expression tree. Operators fold their operands according to `OperatorDef.precedence`
+/// so that e.g. `x + 3 * y` groups as `x + (3 * y)` instead of being evaluated left-to-right.
+#[derive(Debug, Clone)]
+enum Expr {
+    /// A bare literal or identifier, carried through as-is.
+    Literal(String),
+    /// A double-quoted string literal. Kept distinct from `Literal` so codegen can re-emit the
+    /// surrounding quotes and the interpreter never mistakes it for a variable name or number.
+    Str(String),
+    BinaryOp {
+        op: String,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
 }
 
 /// We define a very simple AST that can hold different types of statements.
 #[derive(Debug)]
 enum Statement {
-    /// A simple statement with potential arguments (captured as strings)
-    Simple(String, Vec<String>),
-    /// A block statement: its name (matching a block defined in JSON) and its inner statements.
-    Block(String, Vec<Statement>),
+    /// A simple statement with its arguments parsed as expressions (one per comma-separated region).
+    Simple(String, Vec<Expr>, Span),
+    /// A block statement: its name (matching a block defined in JSON), its inner statements, its span,
+    /// and the condition expression extracted from its start token (e.g. the `x < 10` in `if (x < 10)`).
+    Block(String, Vec<Statement>, Span, Option<Expr>),
+    /// A bare expression statement — a line that didn't match any known statement or block syntax.
+    Expr(Expr, Span),
 }
 
 /// Load the language configuration from a JSON file.
@@ -66,125 +206,724 @@ fn load_config(file_path: &str) -> LanguageConfig {
     config
 }
 
-/// A very simple parser that uses the JSON definitions to build an AST.
-/// It splits the source code into lines and then:
-///   - Checks if the line matches any statement syntax
-///   - Checks for block start/end tokens to build nested stuff
-fn parse_source(source: &str, config: &LanguageConfig) -> Vec<Statement> {
+/// The symbols `lexer::tokenize` should recognize when scanning an argument region: every
+/// operator, the parens that group sub-expressions, and the comma that separates arguments.
+/// Sorted longest-first by `tokenize` itself.
+fn operator_symbols(config: &LanguageConfig) -> Vec<String> {
+    let mut symbols: Vec<String> = config.operators.values().map(|op| op.symbol.clone()).collect();
+    symbols.push("(".to_string());
+    symbols.push(")".to_string());
+    symbols.push(",".to_string());
+    symbols
+}
+
+/// Fold a slice of the whole-source token stream (see `parse_source`) into operand/operator/
+/// paren tokens for the expression parser. Quoted strings come back as single `StringLit`
+/// tokens (so `print "a b"` no longer gets shredded by whitespace splitting), and operator
+/// symbols are matched longest-first by the lexer so `==` isn't shadowed by `=`.
+fn expr_tokens_from(tokens: &[Token]) -> Vec<ExprToken> {
+    tokens
+        .iter()
+        .filter_map(|token| match token.kind {
+            TokenKind::Word => Some(ExprToken::Operand(token.text.clone())),
+            TokenKind::StringLit => Some(ExprToken::StringLit(token.text.clone())),
+            TokenKind::Symbol if token.text == "(" => Some(ExprToken::LParen),
+            TokenKind::Symbol if token.text == ")" => Some(ExprToken::RParen),
+            TokenKind::Symbol if token.text == "," => Some(ExprToken::Comma),
+            TokenKind::Symbol => Some(ExprToken::Op(token.text.clone())),
+            TokenKind::Newline => None,
+        })
+        .collect()
+}
+
+/// Split a token stream on top-level `Comma` tokens (there's no nesting to worry about since
+/// this language has no function calls — a comma is only ever an argument separator).
+fn split_on_commas(tokens: &[ExprToken]) -> Vec<Vec<ExprToken>> {
+    let mut groups: Vec<Vec<ExprToken>> = vec![Vec::new()];
+    for token in tokens {
+        if matches!(token, ExprToken::Comma) {
+            groups.push(Vec::new());
+        } else {
+            groups.last_mut().unwrap().push(token.clone());
+        }
+    }
+    groups
+}
+
+/// One more than the highest `{N}` placeholder index a template references (0 if none),
+/// e.g. 2 for `"{0} = {1};"`.
+fn template_arity(template: &str) -> usize {
+    let bytes = template.as_bytes();
+    let mut max_index: Option<usize> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'{' {
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j].is_ascii_digit() {
+                j += 1;
+            }
+            if j > i + 1 && bytes.get(j) == Some(&b'}') {
+                if let Ok(index) = template[i + 1..j].parse::<usize>() {
+                    max_index = Some(max_index.map_or(index, |m| m.max(index)));
+                }
+            }
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+    max_index.map_or(0, |m| m + 1)
+}
+
+/// Split a statement's argument tokens into one `Expr` per argument. Returns whether every
+/// group's tokens were fully consumed by its expression parse — `false` means some group had
+/// trailing tokens left over (e.g. `print x y`, where `y` isn't part of any operator chain) that
+/// the caller should report as a diagnostic rather than silently dropping.
+///
+/// Comma-separated regions each become their own expression — splitting happens on comma
+/// *tokens* (after tokenization), not on raw `,` bytes, so a quoted string containing a comma
+/// (`print "hello, world"`) survives as a single `StringLit` token instead of being shredded.
+///
+/// When no comma is present but the statement's own template references more than one
+/// placeholder (e.g. assignment's `"{0} = {1};"`), the source follows this language's older
+/// space-separated `name value` convention instead (`let x 1`, not `let x, 1`): the first
+/// token is taken as the bare name and everything after it is parsed as the value expression.
+fn parse_statement_args(tokens: &[ExprToken], template: &str, config: &LanguageConfig) -> (Vec<Expr>, bool) {
+    let groups = split_on_commas(tokens);
+
+    if groups.len() > 1 {
+        let mut fully_consumed = true;
+        let args = groups
+            .iter()
+            .map(|group| {
+                let mut pos = 0;
+                let expr = parse_expr(group, &mut pos, 0, config);
+                fully_consumed &= pos == group.len();
+                expr
+            })
+            .collect();
+        return (args, fully_consumed);
+    }
+
+    let group = &groups[0];
+    if template_arity(template) > 1 && group.len() > 1 {
+        if let Some(ExprToken::Operand(name)) = group.first() {
+            let mut pos = 1;
+            let value = parse_expr(group, &mut pos, 0, config);
+            return (vec![Expr::Literal(name.clone()), value], pos == group.len());
+        }
+    }
+
+    let mut pos = 0;
+    let expr = parse_expr(group, &mut pos, 0, config);
+    (vec![expr], pos == group.len())
+}
+
+/// Parse a single primary expression: a literal/identifier, or a parenthesized sub-expression.
+fn parse_primary(tokens: &[ExprToken], pos: &mut usize, config: &LanguageConfig) -> Expr {
+    match tokens.get(*pos) {
+        Some(ExprToken::LParen) => {
+            *pos += 1;
+            let inner = parse_expr(tokens, pos, 0, config);
+            if matches!(tokens.get(*pos), Some(ExprToken::RParen)) {
+                *pos += 1;
+            }
+            inner
+        }
+        Some(ExprToken::Operand(s)) => {
+            let lit = Expr::Literal(s.clone());
+            *pos += 1;
+            lit
+        }
+        Some(ExprToken::StringLit(s)) => {
+            let lit = Expr::Str(s.clone());
+            *pos += 1;
+            lit
+        }
+        _ => Expr::Literal(String::new()),
+    }
+}
+
+/// Precedence-climbing (Pratt) parser: parse a primary, then keep folding in operators
+/// whose binding power is at least `min_bp`, recursing with `bp + 1` (left-assoc) or `bp` (right-assoc).
+fn parse_expr(tokens: &[ExprToken], pos: &mut usize, min_bp: u8, config: &LanguageConfig) -> Expr {
+    let mut lhs = parse_primary(tokens, pos, config);
+
+    while let Some(ExprToken::Op(symbol)) = tokens.get(*pos) {
+        let symbol = symbol.clone();
+        let op_def = match config.operators.values().find(|op| op.symbol == symbol) {
+            Some(def) => def,
+            None => break,
+        };
+        if op_def.precedence < min_bp {
+            break;
+        }
+
+        *pos += 1;
+        let next_min_bp = if op_def.right_assoc { op_def.precedence } else { op_def.precedence + 1 };
+        let rhs = parse_expr(tokens, pos, next_min_bp, config);
+        lhs = Expr::BinaryOp {
+            op: symbol,
+            left: Box::new(lhs),
+            right: Box::new(rhs),
+        };
+    }
+
+    lhs
+}
+
+/// Split a statement/block syntax string like `"if ("` or `"end if"` into pieces that are matched
+/// token-by-token against the whole-source stream `parse_source` walks, instead of against raw
+/// line-prefix bytes. The split is on whitespace only, so the pieces line up with the token stream
+/// only where the syntax separates its words and symbols with spaces: `"if ("` matches, `"if("`
+/// does not.
+fn syntax_words(syntax: &str) -> Vec<&str> {
+    syntax.split_whitespace().collect()
+}
+
+/// True if `tokens[pos..]` begins with the word/symbol sequence `words` (a verbatim text match
+/// per token) — the token-stream equivalent of `line.starts_with(prefix)`.
+fn matches_words(tokens: &[Token], pos: usize, words: &[&str]) -> bool {
+    if pos + words.len() > tokens.len() {
+        return false;
+    }
+    words.iter().enumerate().all(|(i, word)| tokens[pos + i].text == *word)
+}
+
+/// The `Span` covering tokens `first..=last` (inclusive) of the whole-source token stream.
+fn token_span(tokens: &[Token], first: usize, last: usize) -> Span {
+    Span {
+        start: tokens[first].start,
+        end: tokens[last].end,
+    }
+}
+
+/// True if `tokens[pos..]` begins with any statement's syntax, or any block's start or end
+/// token. Used to find where one statement's (or block condition's) argument region ends
+/// without it having an explicit terminator of its own.
+fn matches_any_syntax(tokens: &[Token], pos: usize, config: &LanguageConfig) -> bool {
+    config.statements.values().any(|def| matches_words(tokens, pos, &syntax_words(&def.syntax)))
+        || config.blocks.values().any(|def| {
+            matches_words(tokens, pos, &syntax_words(&def.start))
+                || matches_words(tokens, pos, &syntax_words(&def.end))
+        })
+}
+
+/// Find where the current statement's (or expression's) argument region ends, scanning forward
+/// from `start` in the whole-source token stream: the next newline, or the point where some
+/// other recognized statement/block syntax begins — whichever comes first. This is what lets
+/// several statements (and an inline block's own end token) share a single physical line,
+/// something the old line-based matcher could never see past.
+fn find_region_end(tokens: &[Token], start: usize, config: &LanguageConfig) -> usize {
+    let mut pos = start;
+    while pos < tokens.len() {
+        if tokens[pos].kind == TokenKind::Newline || matches_any_syntax(tokens, pos, config) {
+            return pos;
+        }
+        pos += 1;
+    }
+    pos
+}
+
+/// Extract a block's condition expression, starting right after its start token (e.g. the
+/// `x < 10` in `if (x < 10)`). Returns the condition (`None` if there's nothing to parse),
+/// whether its tokens were fully consumed (see `parse_statement_args`'s doc comment for why that
+/// matters), and the token position just past the whole condition.
+///
+/// When the block's start token ends in `(` (e.g. `"if ("`, as `block_start_has_paren` says), the
+/// condition is delimited by that opening paren's matching
+/// close rather than by `find_region_end`'s newline/syntax boundary: depth is tracked so nested
+/// grouping parens in the condition itself (`if (a > (b + c))`) don't end the scan early, and so
+/// anything *after* the closing paren — another statement sharing the line — is left for the
+/// caller to keep parsing instead of being folded into the condition. A start token with no
+/// trailing `(` (an unusual config) falls back to `find_region_end`'s boundary instead.
+fn extract_condition(
+    tokens: &[Token],
+    start: usize,
+    block_start_has_paren: bool,
+    config: &LanguageConfig,
+) -> (Option<Expr>, bool, usize) {
+    let (cond_tokens, next_pos) = if block_start_has_paren {
+        let mut pos = start;
+        let mut depth = 1;
+        while pos < tokens.len() && depth > 0 {
+            match (tokens[pos].kind, tokens[pos].text.as_str()) {
+                (TokenKind::Symbol, "(") => depth += 1,
+                (TokenKind::Symbol, ")") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            pos += 1;
+        }
+        (&tokens[start..pos.min(tokens.len())], (pos + 1).min(tokens.len()))
+    } else {
+        let region_end = find_region_end(tokens, start, config);
+        (&tokens[start..region_end], region_end)
+    };
+
+    let expr_tokens = expr_tokens_from(cond_tokens);
+    if expr_tokens.is_empty() {
+        return (None, true, next_pos);
+    }
+    let mut pos = 0;
+    let condition = parse_expr(&expr_tokens, &mut pos, 0, config);
+    (Some(condition), pos == expr_tokens.len(), next_pos)
+}
+
+/// Build the AST by walking the whole-source token stream produced by `lexer::tokenize`
+/// (rather than matching prefixes one line at a time), so statement/block/operator recognition
+/// works the same way everywhere: several statements — including an inline block's own end
+/// token — can share a single physical line, and a `Span`'s byte offsets come straight from the
+/// matched tokens instead of being reconstructed from a line's position.
+///
+/// Errors (an end token with no open block, a block still open at EOF, or unconsumed trailing
+/// tokens after a statement/condition/expression) are collected as `Diagnostic`s instead of
+/// aborting on the first one.
+fn parse_source(source: &str, config: &LanguageConfig) -> Result<Vec<Statement>, Vec<Diagnostic>> {
     let mut statements = Vec::new();
-    let mut block_stack: Vec<(String, Vec<Statement>)> = Vec::new(); // (block name, statements inside)
+    // (block name, statements inside, start span, condition expression extracted from the start token)
+    let mut block_stack: Vec<(String, Vec<Statement>, Span, Option<Expr>)> = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    let tokens = lexer::tokenize(source, &operator_symbols(config));
+
+    // Candidates sorted longest-syntax-first, so a syntax that's a prefix of another's (e.g.
+    // "if" vs. "if not", or "end" vs. "end if") never shadows the more specific one.
+    let mut start_candidates: Vec<(&String, &BlockDef)> = config.blocks.iter().collect();
+    start_candidates.sort_by_key(|(_, def)| std::cmp::Reverse(def.start.len()));
+    let mut end_candidates: Vec<(&String, &BlockDef)> = config.blocks.iter().collect();
+    end_candidates.sort_by_key(|(_, def)| std::cmp::Reverse(def.end.len()));
+    let mut statement_candidates: Vec<(&String, &StatementDef)> = config.statements.iter().collect();
+    statement_candidates.sort_by_key(|(_, def)| std::cmp::Reverse(def.syntax.len()));
+
+    let mut pos = 0usize;
+    while pos < tokens.len() {
+        if tokens[pos].kind == TokenKind::Newline {
+            pos += 1;
+            continue;
+        }
 
-    // Iterate through each line of the source code.
-    for line in source.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
         let mut matched = false;
 
         // Check if we are starting a new block.
-        for (block_name, block_def) in &config.blocks {
-            if line.starts_with(&block_def.start) {
-                // Push a new block on the stack.
-                block_stack.push((block_name.clone(), Vec::new()));
+        for &(block_name, block_def) in &start_candidates {
+            let words = syntax_words(&block_def.start);
+            if matches_words(&tokens, pos, &words) {
+                let start_span = token_span(&tokens, pos, pos + words.len() - 1);
+                let cond_start = pos + words.len();
+                let has_paren = block_def.start.trim_end().ends_with('(');
+                let (condition, fully_consumed, next_pos) =
+                    extract_condition(&tokens, cond_start, has_paren, config);
+                if !fully_consumed {
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "unexpected trailing tokens after `{}` block's condition",
+                            block_name
+                        ),
+                        span: start_span,
+                    });
+                }
+                block_stack.push((block_name.clone(), Vec::new(), start_span, condition));
+                pos = next_pos;
                 matched = true;
                 break;
-            } else if !block_stack.is_empty() && line.starts_with(&block_def.end) {
-                // End of the current block.
-                if let Some((bname, inner_statements)) = block_stack.pop() {
-                    let block_stmt = Statement::Block(bname, inner_statements);
-                    if let Some((_, outer)) = block_stack.last_mut() {
+            }
+        }
+        if matched { continue; }
+
+        // An end token can only close the block that's actually open right now — check that
+        // one directly instead of matching against every block definition's end token, so a
+        // stray prefix match from an unrelated block (e.g. a generic "end" shadowing a more
+        // specific "end if") can never pop the wrong block off the stack.
+        if let Some((open_name, _, _, _)) = block_stack.last() {
+            if let Some(open_def) = config.blocks.get(open_name) {
+                let words = syntax_words(&open_def.end);
+                if matches_words(&tokens, pos, &words) {
+                    let (bname, inner_statements, block_span, condition) = block_stack.pop().unwrap();
+                    let block_stmt = Statement::Block(bname, inner_statements, block_span, condition);
+                    if let Some((_, outer, _, _)) = block_stack.last_mut() {
                         outer.push(block_stmt);
                     } else {
                         statements.push(block_stmt);
                     }
+                    pos += words.len();
                     matched = true;
-                    break;
                 }
             }
         }
         if matched { continue; }
 
-        // Check for simple statements
-        for (stmt_name, stmt_def) in &config.statements {
-            if line.starts_with(&stmt_def.syntax) {
-                // Extract arguments after the syntax.
-                // Here we assume arguments are space separated after the syntax.
-                let args_part = line[stmt_def.syntax.len()..].trim();
-                let args: Vec<String> = args_part
-                    .split_whitespace()
-                    .map(|s| s.to_string())
-                    .collect();
-
-                let simple_stmt = Statement::Simple(stmt_name.clone(), args);
-                if let Some((_, block)) = block_stack.last_mut() {
+        // Not the currently-open block's end token (or nothing is open). If it's still *some*
+        // block's end token, report precisely what went wrong instead of falling through to a
+        // bare-expression parse.
+        for &(_block_name, block_def) in &end_candidates {
+            let words = syntax_words(&block_def.end);
+            if matches_words(&tokens, pos, &words) {
+                let span = token_span(&tokens, pos, pos + words.len() - 1);
+                let message = match block_stack.last() {
+                    Some((open_name, ..)) => format!(
+                        "end token `{}` does not close the currently open block `{}` (expected `{}`)",
+                        block_def.end,
+                        open_name,
+                        config.blocks.get(open_name).map(|d| d.end.as_str()).unwrap_or("<unknown>"),
+                    ),
+                    None => format!("unmatched end token `{}`: no block is open here", block_def.end),
+                };
+                diagnostics.push(Diagnostic { message, span });
+                pos += words.len();
+                matched = true;
+                break;
+            }
+        }
+        if matched { continue; }
+
+        // Check for simple statements. Candidates are sorted by syntax length, longest first,
+        // so e.g. a hypothetical "print_raw" syntax is tried before "print" and isn't shadowed by it.
+        for &(stmt_name, stmt_def) in &statement_candidates {
+            let words = syntax_words(&stmt_def.syntax);
+            if matches_words(&tokens, pos, &words) {
+                let stmt_start = pos;
+                let args_start = pos + words.len();
+                let region_end = find_region_end(&tokens, args_start, config);
+
+                // Extract arguments after the syntax: one expression per comma-separated
+                // region, or (with no comma) a bare `name value` pair when the template
+                // expects more than one argument. See `parse_statement_args`.
+                let arg_tokens = expr_tokens_from(&tokens[args_start..region_end]);
+                let (args, fully_consumed) = if arg_tokens.is_empty() {
+                    (Vec::new(), true)
+                } else {
+                    parse_statement_args(&arg_tokens, &stmt_def.template, config)
+                };
+                let span = token_span(&tokens, stmt_start, region_end.saturating_sub(1).max(stmt_start));
+                if !fully_consumed {
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "unexpected trailing tokens after `{}` statement's arguments",
+                            stmt_name
+                        ),
+                        span,
+                    });
+                }
+
+                let simple_stmt = Statement::Simple(stmt_name.clone(), args, span);
+                if let Some((_, block, _, _)) = block_stack.last_mut() {
                     block.push(simple_stmt);
                 } else {
                     statements.push(simple_stmt);
                 }
+                pos = region_end;
                 matched = true;
                 break;
             }
         }
+        if matched { continue; }
 
-        // If nothing matched, treat it as an expression or default print
-        if !matched {
-            println!("No matches found on line: {}", line);
-            let simple_stmt = Statement::Simple("print".to_string(), vec![line.to_string()]);
-            if let Some((_, block)) = block_stack.last_mut() {
-                block.push(simple_stmt);
-            } else {
-                statements.push(simple_stmt);
+        // Nothing matched a statement or a block start/end: treat whatever's left up to the
+        // next recognized boundary as a bare expression.
+        let region_end = find_region_end(&tokens, pos, config);
+        let expr_tokens = expr_tokens_from(&tokens[pos..region_end]);
+        let span = token_span(&tokens, pos, region_end.saturating_sub(1).max(pos));
+        let mut epos = 0;
+        let expr = parse_expr(&expr_tokens, &mut epos, 0, config);
+        if epos != expr_tokens.len() {
+            diagnostics.push(Diagnostic {
+                message: "unexpected trailing tokens after expression".to_string(),
+                span,
+            });
+        }
+        let expr_stmt = Statement::Expr(expr, span);
+        if let Some((_, block, _, _)) = block_stack.last_mut() {
+            block.push(expr_stmt);
+        } else {
+            statements.push(expr_stmt);
+        }
+        // Guard against a zero-width region looping forever instead of making progress.
+        pos = region_end.max(pos + 1);
+    }
+
+    // Anything still on the stack at EOF is an unterminated block.
+    for (block_name, _, block_span, _) in &block_stack {
+        let end_token = config
+            .blocks
+            .get(block_name)
+            .map(|def| def.end.as_str())
+            .unwrap_or("<unknown>");
+        diagnostics.push(Diagnostic {
+            message: format!("unterminated block: expected end token `{}`", end_token),
+            span: *block_span,
+        });
+    }
+
+    if diagnostics.is_empty() {
+        Ok(statements)
+    } else {
+        Err(diagnostics)
+    }
+}
+
+/// Walk an expression tree bottom-up, substituting the generated left/right code into
+/// the matching operator's `{0}`/`{1}` template so nested precedence comes out parenthesized correctly.
+fn generate_expr_code(expr: &Expr, config: &LanguageConfig) -> String {
+    match expr {
+        Expr::Literal(s) => s.clone(),
+        Expr::Str(s) => quote_string(s),
+        Expr::BinaryOp { op, left, right } => {
+            let left_code = generate_expr_code(left, config);
+            let right_code = generate_expr_code(right, config);
+            match config.operators.values().find(|def| &def.symbol == op) {
+                Some(def) => def.template.replace("{0}", &left_code).replace("{1}", &right_code),
+                None => format!("({} {} {})", left_code, op, right_code),
             }
         }
     }
+}
 
-    statements
+/// Re-quote a string literal's content for target source code: backslashes and double quotes are
+/// escaped so the result is a valid double-quoted string literal in either of this project's
+/// backend targets (C and Python-style both use the same `"..."` syntax).
+fn quote_string(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
 }
 
-/// Generate C code from the AST using the JSON templates for each statement or block.
-/// This is a basic implementation: you could extend it to do full template replacement
-/// (e.g. using regex or a templating engine) for more dynamic code generation.
-fn generate_c_code(
+/// If an expression is a bare identifier (as used for the variable name in an assignment), return it.
+fn literal_name(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Literal(s) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+/// Generate source code from the AST for a single backend `target`, reading indentation and
+/// declaration behavior from `TargetDef` instead of hardcoding them per language. This replaces
+/// what used to be separate `generate_c_code`/`generate_python_code` functions.
+fn generate(
     statements: &[Statement],
     config: &LanguageConfig,
+    target: &TargetDef,
     declared_vars: &mut HashSet<String>,
     indent: usize,
 ) -> String {
-    let indent_str = "    ".repeat(indent);
-    let mut c_code = String::new();
+    let indent_str = target.indent.repeat(indent);
+    let mut code = String::new();
 
     for stmt in statements {
         match stmt {
-            Statement::Simple(name, args) => {
+            Statement::Simple(name, args, _) => {
                 // Look up the statement definition by name.
                 if let Some(def) = config.statements.get(name) {
-                    // Do a simple replacement: {0}, {1}, etc.
+                    // Do a simple replacement: {0}, {1}, etc., using the generated code for each argument.
                     let mut line = def.template.clone();
                     for (i, arg) in args.iter().enumerate() {
                         let placeholder = format!("{{{}}}", i);
-                        line = line.replace(&placeholder, arg);
+                        line = line.replace(&placeholder, &generate_expr_code(arg, config));
                     }
-                    // For assignments, ensure variables are declared only once.
+                    // For assignments, emit the target's declaration once per variable (if it has one at all).
                     if name == "assignment" && !args.is_empty() {
-                        let var_name = &args[0];
-                        if !declared_vars.contains(var_name) {
-                            c_code.push_str(&format!("{}int {};\n", indent_str, var_name));
-                            declared_vars.insert(var_name.clone());
+                        if let (Some(var_name), Some(decl)) = (literal_name(&args[0]), &target.declaration) {
+                            if !declared_vars.contains(var_name) {
+                                let ty = decl.type_map.get("default").map(String::as_str).unwrap_or("");
+                                let decl_code = decl.template.replace("{type}", ty).replace("{0}", var_name);
+                                code.push_str(&format!("{}{}\n", indent_str, decl_code));
+                                declared_vars.insert(var_name.to_string());
+                            }
                         }
                     }
-                    c_code.push_str(&format!("{}{}\n", indent_str, line));
+                    code.push_str(&format!("{}{}\n", indent_str, line));
                 }
             }
-            Statement::Block(name, inner) => {
+            Statement::Block(name, inner, _, condition) => {
                 if let Some(def) = config.blocks.get(name) {
                     // Use the block template.
-                    // We assume the template has placeholders like {body} that we fill in recursively.
-                    let inner_code = generate_c_code(inner, config, declared_vars, indent + 1);
-                    let block_code = def.template.replace("{body}", &inner_code);
-                    c_code.push_str(&format!("{}{}\n", indent_str, block_code));
+                    // We assume the template has placeholders like {body} and {condition} that we fill in.
+                    let inner_code = generate(inner, config, target, declared_vars, indent + 1);
+                    let condition_code = condition
+                        .as_ref()
+                        .map(|c| generate_expr_code(c, config))
+                        .unwrap_or_default();
+                    let block_code = def
+                        .template
+                        .replace("{body}", &inner_code)
+                        .replace("{condition}", &condition_code);
+                    code.push_str(&format!("{}{}\n", indent_str, block_code));
                 }
             }
+            Statement::Expr(expr, _) => {
+                code.push_str(&format!("{}{};\n", indent_str, generate_expr_code(expr, config)));
+            }
         }
     }
 
-    c_code
+    code
+}
+
+/// A runtime value produced while interpreting a program directly, instead of transpiling it.
+#[derive(Debug, Clone)]
+enum Value {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+}
+
+impl Value {
+    fn display(&self) -> String {
+        match self {
+            Value::Number(n) if n.fract() == 0.0 => format!("{}", *n as i64),
+            Value::Number(n) => format!("{}", n),
+            Value::Str(s) => s.clone(),
+            Value::Bool(b) => b.to_string(),
+        }
+    }
+}
+
+/// Truthiness for `if`/`while` conditions: nonzero numbers, nonempty strings, and `true`.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => *n != 0.0,
+        Value::Str(s) => !s.is_empty(),
+        Value::Bool(b) => *b,
+    }
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x == y,
+        (Value::Str(x), Value::Str(y)) => x == y,
+        (Value::Bool(x), Value::Bool(y)) => x == y,
+        _ => false,
+    }
+}
+
+/// Evaluate an expression against the current variable environment.
+fn eval_expr(expr: &Expr, env: &HashMap<String, Value>) -> Value {
+    match expr {
+        Expr::Literal(s) => {
+            if let Some(value) = env.get(s) {
+                value.clone()
+            } else if let Ok(n) = s.parse::<f64>() {
+                Value::Number(n)
+            } else {
+                Value::Str(s.clone())
+            }
+        }
+        Expr::Str(s) => Value::Str(s.clone()),
+        Expr::BinaryOp { op, left, right } => {
+            let l = eval_expr(left, env);
+            let r = eval_expr(right, env);
+            apply_operator(op, &l, &r)
+        }
+    }
+}
+
+/// Give each operator symbol its native runtime meaning, independent of its codegen template.
+fn apply_operator(op: &str, l: &Value, r: &Value) -> Value {
+    match (op, l, r) {
+        ("+", Value::Number(a), Value::Number(b)) => Value::Number(a + b),
+        ("+", Value::Str(a), Value::Str(b)) => Value::Str(format!("{}{}", a, b)),
+        ("-", Value::Number(a), Value::Number(b)) => Value::Number(a - b),
+        ("*", Value::Number(a), Value::Number(b)) => Value::Number(a * b),
+        ("/", Value::Number(a), Value::Number(b)) => Value::Number(a / b),
+        ("==", _, _) => Value::Bool(values_equal(l, r)),
+        ("!=", _, _) => Value::Bool(!values_equal(l, r)),
+        ("<", Value::Number(a), Value::Number(b)) => Value::Bool(a < b),
+        (">", Value::Number(a), Value::Number(b)) => Value::Bool(a > b),
+        ("<=", Value::Number(a), Value::Number(b)) => Value::Bool(a <= b),
+        (">=", Value::Number(a), Value::Number(b)) => Value::Bool(a >= b),
+        _ => Value::Str(format!("{}{}{}", l.display(), op, r.display())),
+    }
+}
+
+/// Walk the AST and execute it directly against `env`, instead of generating source for it.
+/// Assignments mutate `env`; `print` evaluates its argument and writes it to stdout; blocks
+/// branch or loop based on their `kind` and the condition extracted during parsing. `source`
+/// is only needed to render a `Diagnostic` (via each statement's own `Span`) when a statement
+/// can't actually be run — a custom statement the interpreter has no built-in behavior for, or
+/// a block with no condition to evaluate — instead of silently doing nothing.
+fn interpret(statements: &[Statement], config: &LanguageConfig, env: &mut HashMap<String, Value>, source: &str) {
+    for stmt in statements {
+        match stmt {
+            Statement::Simple(name, args, span) => match name.as_str() {
+                "assignment" if args.len() >= 2 => {
+                    if let Some(var_name) = literal_name(&args[0]) {
+                        let value = eval_expr(&args[1], env);
+                        env.insert(var_name.to_string(), value);
+                    }
+                }
+                "print" if !args.is_empty() => {
+                    println!("{}", eval_expr(&args[0], env).display());
+                }
+                _ => eprintln!(
+                    "{}\n",
+                    render_diagnostic(
+                        source,
+                        &Diagnostic {
+                            message: format!(
+                                "--interpret has no runtime behavior for statement `{}` with {} argument(s)",
+                                name,
+                                args.len()
+                            ),
+                            span: *span,
+                        },
+                    )
+                ),
+            },
+            Statement::Block(name, inner, span, condition) => {
+                let def = match config.blocks.get(name) {
+                    Some(def) => def,
+                    None => continue,
+                };
+                let condition = match condition {
+                    Some(c) => c,
+                    None => {
+                        eprintln!(
+                            "{}\n",
+                            render_diagnostic(
+                                source,
+                                &Diagnostic {
+                                    message: format!("block `{}` has no condition to evaluate; skipping", name),
+                                    span: *span,
+                                },
+                            )
+                        );
+                        continue;
+                    }
+                };
+                match def.kind.as_deref() {
+                    Some("loop") => {
+                        while is_truthy(&eval_expr(condition, env)) {
+                            interpret(inner, config, env, source);
+                        }
+                    }
+                    _ => {
+                        if is_truthy(&eval_expr(condition, env)) {
+                            interpret(inner, config, env, source);
+                        }
+                    }
+                }
+            }
+            Statement::Expr(expr, span) => {
+                if matches!(expr, Expr::Literal(s) if s.is_empty()) {
+                    eprintln!(
+                        "{}\n",
+                        render_diagnostic(
+                            source,
+                            &Diagnostic {
+                                message: "expression did not evaluate to anything (malformed expression?)".to_string(),
+                                span: *span,
+                            },
+                        )
+                    );
+                    continue;
+                }
+                eval_expr(expr, env);
+            }
+        }
+    }
 }
 
 /// Write generated code to a file.
@@ -194,53 +933,444 @@ fn write_to_file(code: &str, file_path: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// True if every diagnostic in the set is an "unterminated block" complaint — i.e. the only
+/// thing wrong with the buffer so far is that it's mid-block, not that anything is actually invalid.
+fn only_unterminated_blocks(diagnostics: &[Diagnostic]) -> bool {
+    !diagnostics.is_empty() && diagnostics.iter().all(|d| d.message.starts_with("unterminated block"))
+}
+
+/// Interactive REPL: reads lines from stdin, reusing `parse_source` incrementally. Because
+/// `parse_source` already tracks open blocks via its `block_stack`, we detect "still mid-block"
+/// by re-parsing the accumulated buffer and checking whether its only complaint is an unterminated
+/// block — if so we switch to a continuation prompt and keep buffering instead of executing yet.
+/// Variable state persists across entries via a single `env` for the whole session.
+fn run_repl(config_path: &str) {
+    let config = load_config(config_path);
+    let mut env: HashMap<String, Value> = HashMap::new();
+    let mut last_ast: Vec<Statement> = Vec::new();
+    let mut buffer = String::new();
+
+    let stdin = io::stdin();
+    loop {
+        print!("{}", if buffer.is_empty() { "eidos> " } else { "....> " });
+        io::stdout().flush().ok();
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            break; // EOF (Ctrl-D)
+        }
+
+        if buffer.is_empty() {
+            match line.trim() {
+                ":reset" => {
+                    env.clear();
+                    continue;
+                }
+                ":dump" => {
+                    println!("{:#?}", last_ast);
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        buffer.push_str(&line);
+
+        match parse_source(&buffer, &config) {
+            Err(diagnostics) if only_unterminated_blocks(&diagnostics) => {
+                // Still inside an open block (e.g. a lone `if (...)` line) — keep buffering.
+                continue;
+            }
+            Err(diagnostics) => {
+                for diag in &diagnostics {
+                    eprintln!("{}\n", render_diagnostic(&buffer, diag));
+                }
+            }
+            Ok(ast) => {
+                interpret(&ast, &config, &mut env, &buffer);
+                last_ast = ast;
+            }
+        }
+        buffer.clear();
+    }
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+
+    if raw_args.first().map(String::as_str) == Some("repl") {
+        let config_file = raw_args.get(1).unwrap_or_else(|| {
+            eprintln!("Usage: eidos repl <config_file>");
+            process::exit(1);
+        });
+        run_repl(config_file);
+        return;
+    }
+
     let mut debug = false;
-    let (config_file, source_file) = match args.len() {
-        4 if args[1] == "-d" => {
-            debug = true;
-            (&args[2], &args[3])
-        },
-        3 => (&args[1], &args[2]),
+    let mut interpret_mode = false;
+    let mut target_name: Option<String> = None;
+    let mut positional = Vec::new();
+
+    let mut i = 0;
+    while i < raw_args.len() {
+        match raw_args[i].as_str() {
+            "-d" => debug = true,
+            "--interpret" => interpret_mode = true,
+            "--target" => {
+                i += 1;
+                target_name = raw_args.get(i).cloned();
+            }
+            other => positional.push(other.to_string()),
+        }
+        i += 1;
+    }
+
+    let (config_file, source_file) = match positional.as_slice() {
+        [config_file, source_file] => (config_file.clone(), source_file.clone()),
         _ => {
-            eprintln!("Usage: eidos [-d] <config_file> <source_file>");
+            eprintln!("Usage: eidos [-d] (--interpret | --target <name>) <config_file> <source_file>");
             process::exit(1);
         }
     };
 
     // Load our language definition from JSON.
-    let config = load_config(config_file);
+    let config = load_config(&config_file);
     if debug {
         println!("Language Config Loaded:\n{:#?}", config);
     }
 
     // Read the source code.
-    let source = std::fs::read_to_string(source_file).unwrap_or_else(|_| {
+    let source = std::fs::read_to_string(&source_file).unwrap_or_else(|_| {
         eprintln!("Could not read source file: {}", source_file);
         process::exit(1);
     });
 
     // Parse the source into an AST using our JSON definitions.
-    let ast = parse_source(&source, &config);
+    let ast = match parse_source(&source, &config) {
+        Ok(ast) => ast,
+        Err(diagnostics) => {
+            for diag in &diagnostics {
+                eprintln!("{}\n", render_diagnostic(&source, diag));
+            }
+            process::exit(1);
+        }
+    };
     if debug {
         println!("AST: {:#?}", ast);
     }
 
-    // Generate C code. (You could later swap this out to generate another target language!)
+    if interpret_mode {
+        let mut env: HashMap<String, Value> = HashMap::new();
+        interpret(&ast, &config, &mut env, &source);
+        return;
+    }
+
+    let target_name = target_name.unwrap_or_else(|| {
+        let available: Vec<&str> = config.targets.keys().map(String::as_str).collect();
+        eprintln!("Missing --target <name>. Available targets: {}", available.join(", "));
+        process::exit(1);
+    });
+    let target = config.targets.get(&target_name).unwrap_or_else(|| {
+        let available: Vec<&str> = config.targets.keys().map(String::as_str).collect();
+        eprintln!("Unknown target `{}`. Available targets: {}", target_name, available.join(", "));
+        process::exit(1);
+    });
+
+    // Generate code for the selected backend target.
     let mut declared_vars = HashSet::new();
-    let mut c_code = String::new();
-    c_code.push_str("#include <stdio.h>\n\n");
-    c_code.push_str("int main() {\n");
-    c_code.push_str(&generate_c_code(&ast, &config, &mut declared_vars, 1));
-    c_code.push_str("    return 0;\n");
-    c_code.push_str("}\n");
-
-    // Write the generated code to output.c.
-    if let Err(e) = write_to_file(&c_code, "output.c") {
+    let mut code = String::new();
+    if let Some(comment) = &target.comment {
+        code.push_str(&format!("{} generated by eidos from {}\n", comment, config_file));
+    }
+    code.push_str(&target.preamble);
+    code.push_str(&generate(&ast, &config, target, &mut declared_vars, 1));
+    code.push_str(&target.epilogue);
+
+    // Write the generated code to an output file named after the target's extension.
+    let output_path = format!("output.{}", target.extension);
+    if let Err(e) = write_to_file(&code, &output_path) {
         eprintln!("Error writing output: {}", e);
         process::exit(1);
     }
 
-    println!("C code generated in output.c");
+    println!("{} code generated in {}", target_name, output_path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> LanguageConfig {
+        serde_json::from_str(
+            r#"{
+                "statements": {
+                    "assignment": {"syntax": "let ", "template": "{0} = {1};"},
+                    "print": {"syntax": "print ", "template": "print({0});"}
+                },
+                "blocks": {},
+                "operators": {
+                    "add": {"symbol": "+", "template": "({0} + {1})", "precedence": 1}
+                },
+                "targets": {}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn assignment_without_comma_keeps_both_arguments() {
+        let config = test_config();
+        let ast = parse_source("let x 1\n", &config).expect("should parse");
+        let Statement::Simple(name, args, _) = &ast[0] else { panic!("expected Simple") };
+        assert_eq!(name, "assignment");
+        assert_eq!(args.len(), 2);
+        assert!(matches!(&args[0], Expr::Literal(s) if s == "x"));
+        assert!(matches!(&args[1], Expr::Literal(s) if s == "1"));
+    }
+
+    #[test]
+    fn quoted_string_with_comma_is_not_shredded() {
+        let config = test_config();
+        let ast = parse_source("print \"hello, world\"\n", &config).expect("should parse");
+        let Statement::Simple(name, args, _) = &ast[0] else { panic!("expected Simple") };
+        assert_eq!(name, "print");
+        assert_eq!(args.len(), 1);
+        assert!(matches!(&args[0], Expr::Str(s) if s == "hello, world"));
+    }
+
+    #[test]
+    fn string_literals_keep_their_quotes_through_codegen() {
+        let config = test_config();
+        let ast = parse_source("print \"hello\"\n", &config).expect("should parse");
+        let Statement::Simple(name, args, _) = &ast[0] else { panic!("expected Simple") };
+        assert_eq!(name, "print");
+        assert_eq!(generate_expr_code(&args[0], &config), "\"hello\"");
+    }
+
+    #[test]
+    fn comma_still_splits_multiple_arguments() {
+        let config = test_config();
+        let ast = parse_source("print x, y\n", &config).expect("should parse");
+        let Statement::Simple(_, args, _) = &ast[0] else { panic!("expected Simple") };
+        assert_eq!(args.len(), 2);
+        assert!(matches!(&args[0], Expr::Literal(s) if s == "x"));
+        assert!(matches!(&args[1], Expr::Literal(s) if s == "y"));
+    }
+
+    #[test]
+    fn trailing_tokens_after_an_argument_are_reported_not_dropped() {
+        let config = test_config();
+        // "y" isn't part of any operator chain starting from "x", so it's unconsumed garbage —
+        // it must not be silently dropped the way it used to be.
+        let err = parse_source("print x y\n", &config).expect_err("should not parse");
+        assert!(err.iter().any(|d| d.message.contains("unexpected trailing tokens")));
+    }
+
+    fn block_config() -> LanguageConfig {
+        serde_json::from_str(
+            r#"{
+                "statements": {},
+                "blocks": {
+                    "if": {"start": "if (", "end": "end if", "template": "if ({condition}) {\n{body}\n}"},
+                    "loop": {"start": "while (", "end": "end", "template": "while ({condition}) {\n{body}\n}"}
+                },
+                "operators": {},
+                "targets": {}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn end_token_only_closes_the_block_actually_open() {
+        let config = block_config();
+        // "end" is the "loop" block's end token and a prefix of "if"'s ("end if"). With no
+        // "loop" block open, it must not be able to close the open "if" block.
+        let err = parse_source("if (x)\nend\n", &config).expect_err("should not parse");
+        assert!(err.iter().any(|d| d.message.contains("does not close the currently open block")));
+    }
+
+    #[test]
+    fn matching_end_token_closes_its_own_block() {
+        let config = block_config();
+        let ast = parse_source("if (x)\nend if\n", &config).expect("should parse");
+        assert!(matches!(&ast[0], Statement::Block(name, _, _, _) if name == "if"));
+    }
+
+    fn full_config() -> LanguageConfig {
+        serde_json::from_str(
+            r#"{
+                "statements": {
+                    "print": {"syntax": "print ", "template": "print({0});"}
+                },
+                "blocks": {
+                    "if": {"start": "if (", "end": "end if", "template": "if ({condition}) {\n{body}\n}"}
+                },
+                "operators": {
+                    "gt": {"symbol": ">", "template": "({0} > {1})", "precedence": 1}
+                },
+                "targets": {}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn a_blocks_condition_statement_and_end_token_can_share_one_line() {
+        // Statement/block recognition now matches against the whole-source token stream instead
+        // of one line-prefix at a time, so an inline block like this parses as a single "if"
+        // block containing one "print" statement, rather than failing with an unterminated-block
+        // diagnostic the way it used to when everything had to be on its own line.
+        let config = full_config();
+        let ast = parse_source("if (1) print \"x\" end if\n", &config).expect("should parse");
+        assert_eq!(ast.len(), 1);
+        let Statement::Block(name, inner, _, condition) = &ast[0] else { panic!("expected Block") };
+        assert_eq!(name, "if");
+        assert!(matches!(condition, Some(Expr::Literal(s)) if s == "1"));
+        assert_eq!(inner.len(), 1);
+        let Statement::Simple(stmt_name, args, _) = &inner[0] else { panic!("expected Simple") };
+        assert_eq!(stmt_name, "print");
+        assert!(matches!(&args[0], Expr::Str(s) if s == "x"));
+    }
+
+    #[test]
+    fn a_nested_condition_paren_does_not_end_the_condition_early() {
+        let config = full_config();
+        let ast = parse_source("if (1 > (2 > 3)) print \"x\" end if\n", &config).expect("should parse");
+        let Statement::Block(_, _, _, condition) = &ast[0] else { panic!("expected Block") };
+        assert!(matches!(condition, Some(Expr::BinaryOp { .. })));
+    }
+
+    fn codegen_config() -> LanguageConfig {
+        serde_json::from_str(
+            r##"{
+                "statements": {
+                    "assignment": {"syntax": "let ", "template": "{0} = {1};"},
+                    "print": {"syntax": "print ", "template": "printf(\"%s\\n\", {0});"}
+                },
+                "blocks": {
+                    "if": {"start": "if (", "end": "end if", "template": "if ({condition}) {\n{body}\n}", "kind": "conditional"}
+                },
+                "operators": {
+                    "gt": {"symbol": ">", "template": "({0} > {1})", "precedence": 1}
+                },
+                "targets": {
+                    "c": {
+                        "extension": "c",
+                        "preamble": "#include <stdio.h>\nint main() {\n",
+                        "epilogue": "return 0;\n}\n",
+                        "indent": "  ",
+                        "declaration": {"template": "{type} {0};", "type_map": {"default": "int"}}
+                    }
+                }
+            }"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn generate_emits_a_declaration_once_per_variable() {
+        let config = codegen_config();
+        let ast = parse_source("let x 1\nlet x 2\nprint x\n", &config).expect("should parse");
+        let target = &config.targets["c"];
+        let mut declared_vars = HashSet::new();
+        let code = generate(&ast, &config, target, &mut declared_vars, 1);
+        assert_eq!(code.matches("int x;").count(), 1);
+        assert_eq!(code.matches("x = 1;").count(), 1);
+        assert_eq!(code.matches("x = 2;").count(), 1);
+        assert!(code.contains("printf(\"%s\\n\", x);"));
+    }
+
+    #[test]
+    fn generate_nests_a_blocks_body_under_its_own_template() {
+        let config = codegen_config();
+        let ast = parse_source("if (x > 1) print x end if\n", &config).expect("should parse");
+        let target = &config.targets["c"];
+        let mut declared_vars = HashSet::new();
+        let code = generate(&ast, &config, target, &mut declared_vars, 1);
+        assert!(code.contains("if ((x > 1)) {"));
+        assert!(code.contains("printf(\"%s\\n\", x);"));
+    }
+
+    fn interp_config() -> LanguageConfig {
+        serde_json::from_str(
+            r#"{
+                "statements": {
+                    "assignment": {"syntax": "let ", "template": "{0} = {1};"},
+                    "print": {"syntax": "print ", "template": "print({0});"}
+                },
+                "blocks": {
+                    "if": {"start": "if (", "end": "end if", "template": "if ({condition}) {\n{body}\n}"},
+                    "while": {"start": "while (", "end": "end", "template": "while ({condition}) {\n{body}\n}", "kind": "loop"}
+                },
+                "operators": {
+                    "gt": {"symbol": ">", "template": "({0} > {1})", "precedence": 1},
+                    "minus": {"symbol": "-", "template": "({0} - {1})", "precedence": 1}
+                },
+                "targets": {}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn assignment_sets_a_variable_in_the_environment() {
+        let config = interp_config();
+        let source = "let x 5\n";
+        let ast = parse_source(source, &config).expect("should parse");
+        let mut env = HashMap::new();
+        interpret(&ast, &config, &mut env, source);
+        assert!(matches!(env.get("x"), Some(Value::Number(n)) if *n == 5.0));
+    }
+
+    #[test]
+    fn if_block_runs_its_body_only_when_the_condition_is_truthy() {
+        let config = interp_config();
+        let source = "let x 0\nif (x) let x 1\nend if\n";
+        let ast = parse_source(source, &config).expect("should parse");
+        let mut env = HashMap::new();
+        interpret(&ast, &config, &mut env, source);
+        // x started falsy (0), so the body assigning x to 1 must never have run.
+        assert!(matches!(env.get("x"), Some(Value::Number(n)) if *n == 0.0));
+    }
+
+    #[test]
+    fn loop_block_reruns_its_body_until_the_condition_goes_false() {
+        let config = interp_config();
+        let source = "let count 3\nwhile (count > 0) let count (count - 1) end\n";
+        let ast = parse_source(source, &config).expect("should parse");
+        let mut env = HashMap::new();
+        interpret(&ast, &config, &mut env, source);
+        assert!(matches!(env.get("count"), Some(Value::Number(n)) if *n == 0.0));
+    }
+
+    #[test]
+    fn only_unterminated_blocks_is_false_if_any_other_diagnostic_is_present() {
+        let config = interp_config();
+        // A lone "if (" with no end token: the only complaint should be the unterminated block.
+        let unterminated = parse_source("if (1)\n", &config).expect_err("should not parse");
+        assert!(only_unterminated_blocks(&unterminated));
+
+        // Add a second, unrelated problem (a stray "end" the "while" block's end token, with
+        // nothing of that kind open): now it's not *only* unterminated blocks.
+        let mixed = parse_source("if (1)\nend\n", &config).expect_err("should not parse");
+        assert!(!only_unterminated_blocks(&mixed));
+    }
+
+    #[test]
+    fn repl_style_buffering_accumulates_across_lines_until_the_block_closes() {
+        // Mirrors what run_repl does: keep re-parsing the growing buffer while parse_source's
+        // only complaint is an unterminated block, and stop buffering once it actually parses.
+        let config = interp_config();
+        let mut buffer = String::from("if (1)\n");
+        assert!(matches!(parse_source(&buffer, &config), Err(d) if only_unterminated_blocks(&d)));
+
+        buffer.push_str("print 1\n");
+        assert!(matches!(parse_source(&buffer, &config), Err(d) if only_unterminated_blocks(&d)));
+
+        buffer.push_str("end if\n");
+        let ast = parse_source(&buffer, &config).expect("should parse once the block is closed");
+        assert_eq!(ast.len(), 1);
+    }
 }